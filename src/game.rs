@@ -1,50 +1,115 @@
 use std::borrow::Borrow;
 use std::collections::HashSet;
+use std::ffi::{c_void, CStr};
 use std::f32::consts;
 use std::mem::size_of;
 use std::ptr;
 use std::time::Instant;
-use gl::types::{GLint, GLsizei, GLsizeiptr, GLuint, GLvoid};
+use gl::types::{GLchar, GLenum, GLint, GLsizei, GLsizeiptr, GLuint, GLvoid};
 use image::DynamicImage::ImageRgba32F;
 use image::{EncodableLayout, GenericImageView, Rgba32FImage};
 use image::imageops::FilterType;
 use rand::thread_rng;
-use sdl2::{EventPump, Sdl, VideoSubsystem};
+use sdl2::{AudioSubsystem, EventPump, Sdl, VideoSubsystem};
+use sdl2::audio::{AudioCallback as SdlAudioCallback, AudioDevice, AudioSpecDesired};
 use sdl2::event::{Event, WindowEvent};
-use sdl2::keyboard::Scancode;
 use sdl2::mouse::MouseWheelDirection;
 use sdl2::sys::rand;
 use sdl2::video::{GLContext, GLProfile, Window};
 
 use crate::render::color::{ColorInternal, ColorRepr};
 use crate::render::GlDataType;
+use crate::render::input::{Input, Key, MouseButton};
 use crate::render::shader::{ComputeProgram, Program, Shader};
 use crate::render::texture::{ImageAccess, Texture, TextureTarget};
 use crate::render::vertex_arrays::{AttributeLayout, VertexArrayObject};
 use crate::render::buffer::{BufferObject, BufferType, BufferUsage};
 use crate::state::{GameState};
 
-const PARTICLE_COUNT: usize = 1000;
+pub(crate) const PARTICLE_COUNT: usize = 1000;
+
+thread_local! {
+    static GAME_ALIVE: std::cell::Cell<bool> = std::cell::Cell::new(false);
+}
+
+type DebugCallback = Box<dyn FnMut(GLenum, GLenum, GLuint, GLenum, String)>;
+
+/// Called on SDL's audio thread (separate from the single-threaded GL path) to fill one buffer
+/// of interleaved float samples.
+pub type AudioCallback = Box<dyn FnMut(u32, &mut [f32]) + Send>;
+
+struct AudioCallbackAdapter {
+    sample_rate: u32,
+    callback: AudioCallback,
+}
+
+impl SdlAudioCallback for AudioCallbackAdapter {
+    type Channel = f32;
+
+    fn callback(&mut self, out: &mut [f32]) {
+        (self.callback)(self.sample_rate, out);
+    }
+}
+
+extern "system" fn debug_message_trampoline(
+    source: GLenum,
+    gl_type: GLenum,
+    id: GLuint,
+    severity: GLenum,
+    _length: GLsizei,
+    message: *const GLchar,
+    user_param: *mut c_void,
+) {
+    unsafe {
+        let message = CStr::from_ptr(message).to_string_lossy().into_owned();
+        let callback = &mut *(user_param as *mut DebugCallback);
+        callback(source, gl_type, id, severity, message);
+    }
+}
 
 pub struct Game {
     sdl: Sdl,
     video_subsystem: VideoSubsystem,
+    audio_subsystem: AudioSubsystem,
     window: Window,
     event_pump: EventPump,
     gl_context: GLContext,
     running: bool,
     state: Option<GameState>,
     window_size: (i32, i32),
-    keys_down: HashSet<Scancode>,
+    input: Input,
+    gl_version: (i32, i32),
+    gl_extensions: HashSet<String>,
+    debug_callback: Option<Box<DebugCallback>>,
+    audio_device: Option<AudioDevice<AudioCallbackAdapter>>,
 }
 
 
 impl Game {
     pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
+        // `Program`'s current-program cache (see `render::shader`) is a thread-local keyed to
+        // "the one GL context this thread is using", not to a specific `Game` instance. That's
+        // only sound if a single `Game` is ever alive per thread at a time; enforce it here
+        // rather than let a second concurrent `Game` silently read the first one's cached id.
+        GAME_ALIVE.with(|alive| {
+            assert!(!alive.get(), "only one Game may exist per thread at a time");
+            alive.set(true);
+        });
+
+        let game = Self::new_inner();
+        if game.is_err() {
+            GAME_ALIVE.with(|alive| alive.set(false));
+        }
+        game
+    }
+
+    fn new_inner() -> Result<Self, Box<dyn std::error::Error>> {
         let sdl = sdl2::init()?;
 
         let video_subsystem = sdl.video()?;
 
+        let audio_subsystem = sdl.audio()?;
+
         let event_pump = sdl.event_pump()?;
 
         // set GL versions
@@ -53,6 +118,10 @@ impl Game {
 
         gl_attr.set_context_version(4, 3);
 
+        // Required for KHR_debug: without the debug context bit, conformant drivers are free to
+        // never call the callback registered in `set_debug_callback`.
+        gl_attr.set_context_flags().debug().set();
+
         let display = video_subsystem.desktop_display_mode(0).unwrap();
 
         // create window
@@ -70,16 +139,64 @@ impl Game {
             sdl,
             gl_context,
             video_subsystem,
+            audio_subsystem,
             window_size: (window.size().0 as i32, window.size().1 as i32),
             window,
             event_pump,
             running: true,
             state: None,
-            keys_down: HashSet::new(),
+            input: Input::new(),
+            gl_version: (0, 0),
+            gl_extensions: HashSet::new(),
+            debug_callback: None,
+            audio_device: None,
         })
     }
 
+    /// Opens the audio device and starts invoking `callback` on SDL's audio thread to fill each
+    /// buffer of interleaved float samples. `Game` keeps the opened `AudioDevice` so it keeps
+    /// running for the lifetime of the game; playback starts paused, see `resume_audio`.
+    pub fn open_audio(
+        &mut self,
+        sample_rate: i32,
+        channels: u8,
+        callback: impl FnMut(u32, &mut [f32]) + Send + 'static,
+    ) -> Result<(), String> {
+        let desired_spec = AudioSpecDesired {
+            freq: Some(sample_rate),
+            channels: Some(channels),
+            samples: None,
+        };
+
+        let callback: AudioCallback = Box::new(callback);
+
+        let device = self.audio_subsystem.open_playback(None, &desired_spec, |spec| {
+            AudioCallbackAdapter {
+                sample_rate: spec.freq as u32,
+                callback,
+            }
+        })?;
+
+        self.audio_device = Some(device);
+
+        Ok(())
+    }
+
+    pub fn resume_audio(&self) {
+        if let Some(device) = &self.audio_device {
+            device.resume();
+        }
+    }
+
+    pub fn pause_audio(&self) {
+        if let Some(device) = &self.audio_device {
+            device.pause();
+        }
+    }
+
     pub fn handle_events(&mut self) {
+        self.input.begin_frame();
+
         for event in self.event_pump.poll_iter() {
 
 
@@ -94,27 +211,89 @@ impl Game {
 
                 // Window Events
                 Event::Window { win_event, .. } => match win_event {
-                    WindowEvent::Resized(width, height) => self.window_size = (width, height),
+                    WindowEvent::Resized(width, height) => self.resize_window((width, height)),
                     _ => {}
                 },
-                Event::KeyDown { scancode, .. } => if let Some(scancode) = scancode {
-                    self.keys_down.insert(scancode);
+                Event::KeyDown { scancode, .. } => if let Some(key) = scancode.and_then(Key::from_sdl) {
+                    self.input.on_key_down(key);
                 },
-                Event::KeyUp { scancode, .. } => if let Some(scancode) = scancode {
-                    self.keys_down.remove(&scancode);
+                Event::KeyUp { scancode, .. } => if let Some(key) = scancode.and_then(Key::from_sdl) {
+                    self.input.on_key_up(key);
                 },
+                Event::MouseMotion { x, y, xrel, yrel, .. } => {
+                    self.input.on_mouse_motion(x, y, xrel, yrel);
+                }
+                Event::MouseButtonDown { mouse_btn, .. } => if let Some(button) = MouseButton::from_sdl(mouse_btn) {
+                    self.input.on_mouse_button_down(button);
+                },
+                Event::MouseButtonUp { mouse_btn, .. } => if let Some(button) = MouseButton::from_sdl(mouse_btn) {
+                    self.input.on_mouse_button_up(button);
+                },
+                Event::MouseWheel { y, direction, .. } => {
+                    let sign = if direction == MouseWheelDirection::Flipped { -1. } else { 1. };
+                    self.input.on_scroll(y as f32 * sign);
+                }
                 _ => {}
             }
         }
     }
 
     pub unsafe fn init(&mut self) {
+        crate::render::shader::reset_current_program_cache();
+
         gl::Viewport(0, 0, self.window_size.0 as i32, self.window_size.1 as i32);
         gl::ClearColor(0.2, 0.2, 0.2, 1.0);
 
+        self.query_gl_info();
+
         self.state = Some(GameState::new(self));
     }
 
+    /// Registers a KHR_debug callback that receives `(source, type, id, severity, message)`
+    /// for every driver-reported event. Requires a debug context (see `GLProfile`/context flags).
+    pub fn set_debug_callback(&mut self, callback: impl FnMut(GLenum, GLenum, GLuint, GLenum, String) + 'static) {
+        let boxed: Box<DebugCallback> = Box::new(Box::new(callback));
+        let user_param = Box::into_raw(boxed) as *mut c_void;
+
+        unsafe {
+            gl::Enable(gl::DEBUG_OUTPUT);
+            gl::Enable(gl::DEBUG_OUTPUT_SYNCHRONOUS);
+            gl::DebugMessageCallback(Some(debug_message_trampoline), user_param);
+
+            self.debug_callback = Some(Box::from_raw(user_param as *mut DebugCallback));
+        }
+    }
+
+    fn query_gl_info(&mut self) {
+        unsafe {
+            let version = CStr::from_ptr(gl::GetString(gl::VERSION) as *const GLchar)
+                .to_string_lossy();
+            let mut parts = version.split(|c: char| c == '.' || c == ' ');
+            let major = parts.next().and_then(|s| s.parse().ok()).unwrap_or(0);
+            let minor = parts.next().and_then(|s| s.parse().ok()).unwrap_or(0);
+            self.gl_version = (major, minor);
+
+            let mut num_extensions = 0;
+            gl::GetIntegerv(gl::NUM_EXTENSIONS, &mut num_extensions);
+
+            self.gl_extensions = (0..num_extensions)
+                .map(|i| {
+                    let ext = gl::GetStringi(gl::EXTENSIONS, i as GLuint) as *const GLchar;
+                    CStr::from_ptr(ext).to_string_lossy().into_owned()
+                })
+                .collect();
+        }
+    }
+
+    #[inline]
+    pub const fn gl_version(&self) -> (i32, i32) {
+        self.gl_version
+    }
+
+    pub fn supports(&self, ext: &str) -> bool {
+        self.gl_extensions.contains(ext)
+    }
+
     pub fn update(&mut self, delta: f64) {
         if let Some(mut state) = self.state.take() {
             state.update(self, delta);
@@ -142,10 +321,29 @@ impl Game {
         unsafe {
             gl::Viewport(0, 0, size.0, size.1);
         }
+
+        if let Some(state) = &mut self.state {
+            state.resize(size);
+        }
     }
 
-    pub fn is_key_down(&self, key: Scancode) -> bool {
-        self.keys_down.contains(&key)
+    #[inline]
+    pub const fn input(&self) -> &Input {
+        &self.input
     }
+
     pub const fn window_size(&self) -> (i32, i32) { self.window_size }
+}
+
+impl Drop for Game {
+    fn drop(&mut self) {
+        if self.debug_callback.take().is_some() {
+            unsafe {
+                gl::DebugMessageCallback(None, ptr::null());
+            }
+        }
+
+        crate::render::shader::reset_current_program_cache();
+        GAME_ALIVE.with(|alive| alive.set(false));
+    }
 }
\ No newline at end of file
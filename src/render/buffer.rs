@@ -1,12 +1,13 @@
 use std::marker::PhantomData;
 use std::{mem, ptr, slice};
 use std::mem::size_of;
-use gl::types::{GLenum, GLintptr, GLsizei, GLsizeiptr, GLuint, GLvoid};
+use gl::types::{GLbitfield, GLenum, GLintptr, GLsizei, GLsizeiptr, GLsync, GLuint, GLvoid};
 
 pub struct BufferObject<T> where T: Sized {
     id: GLuint,
     kind: BufferType,
     size: GLsizeiptr,
+    persistent_map: Option<*mut T>,
     _owns_t: PhantomData<T>,
 }
 
@@ -49,10 +50,43 @@ impl<T> BufferObject<T> {
             id: bo,
             size: 0,
             kind,
+            persistent_map: None,
             _owns_t: PhantomData::default(),
         }
     }
 
+    /// Allocates a buffer with `glBufferStorage` and maps it once with
+    /// `GL_MAP_PERSISTENT_BIT | GL_MAP_COHERENT_BIT`, returning a long-lived mapping the caller
+    /// can write into every frame via `persistent_slice` without re-mapping. Pair writes with a
+    /// `lock`/`wait` section (see below) so the CPU doesn't race an in-flight draw.
+    pub fn persistent(kind: BufferType, len: usize) -> Self where T: Sized {
+        let mut buff = Self::gen(1, kind);
+        let size = (len * size_of::<T>()) as GLsizeiptr;
+        buff.size = size;
+
+        const FLAGS: GLbitfield = gl::MAP_WRITE_BIT | gl::MAP_PERSISTENT_BIT | gl::MAP_COHERENT_BIT;
+
+        buff.bind();
+        unsafe {
+            gl::BufferStorage(buff.kind as GLenum, size, ptr::null(), FLAGS);
+            let addr = gl::MapBufferRange(buff.kind as GLenum, 0, size, FLAGS) as *mut T;
+            buff.persistent_map = Some(addr);
+        }
+        buff.unbind();
+
+        buff
+    }
+
+    /// The persistently-mapped slice backing this buffer. Panics if the buffer wasn't created
+    /// with `BufferObject::persistent`.
+    pub fn persistent_slice(&mut self) -> &mut [T] {
+        let addr = self.persistent_map
+            .expect("buffer was not created with BufferObject::persistent");
+        let len = self.size as usize / size_of::<T>();
+
+        unsafe { slice::from_raw_parts_mut(addr, len) }
+    }
+
     pub fn bind(&self) {
         unsafe {
             gl::BindBuffer(self.kind as GLenum, self.id);
@@ -75,27 +109,65 @@ impl<T> BufferObject<T> {
     }
 
     pub fn read_slice(&mut self, reader: fn(&[T])) {
+        self.bind();
         unsafe {
-            let addr = gl::MapBuffer(self.id, gl::READ_ONLY) as *const T;
+            let addr = gl::MapBuffer(self.kind as GLenum, gl::READ_ONLY) as *const T;
 
-            let slice = slice::from_raw_parts(addr, self.size as usize);
+            let slice = slice::from_raw_parts(addr, self.size as usize / size_of::<T>());
 
             reader(slice);
 
-            gl::UnmapBuffer(self.id);
+            gl::UnmapBuffer(self.kind as GLenum);
         }
+        self.unbind();
     }
 
     pub fn read_write_slice(&mut self, reader: fn(&mut [T])) {
+        self.bind();
         unsafe {
-            let addr = gl::MapBuffer(self.id, gl::READ_WRITE) as *mut T;
+            let addr = gl::MapBuffer(self.kind as GLenum, gl::READ_WRITE) as *mut T;
 
-            let slice = slice::from_raw_parts_mut(addr, self.size as usize);
+            let slice = slice::from_raw_parts_mut(addr, self.size as usize / size_of::<T>());
 
             reader(slice);
 
-            gl::UnmapBuffer(self.id);
+            gl::UnmapBuffer(self.kind as GLenum);
+        }
+        self.unbind();
+    }
+
+    /// Maps `[offset, offset + len)` elements via `glMapBufferRange`. The returned slice must be
+    /// released with `unmap` before the buffer is bound for anything else.
+    pub fn map_range(&mut self, offset: usize, len: usize, flags: GLbitfield) -> &mut [T] {
+        self.bind();
+        unsafe {
+            let addr = gl::MapBufferRange(
+                self.kind as GLenum,
+                (offset * size_of::<T>()) as GLintptr,
+                (len * size_of::<T>()) as GLsizeiptr,
+                flags,
+            ) as *mut T;
+
+            slice::from_raw_parts_mut(addr, len)
+        }
+    }
+
+    pub fn unmap(&self) {
+        unsafe {
+            gl::UnmapBuffer(self.kind as GLenum);
+        }
+        self.unbind();
+    }
+
+    /// Re-specifies storage with `glBufferData(..., null, usage)`, discarding the previous
+    /// contents so the driver can hand back fresh storage instead of stalling the CPU on a GPU
+    /// that's still reading the old contents. Use before each per-frame streaming upload.
+    pub fn orphan(&mut self, usage: BufferUsage) {
+        self.bind();
+        unsafe {
+            gl::BufferData(self.kind as GLenum, self.size, ptr::null(), usage as GLenum);
         }
+        self.unbind();
     }
 
     pub fn copy_all_to(&self, dest: &mut BufferObject<T>) {
@@ -129,6 +201,41 @@ impl<T> BufferObject<T> {
     }
 }
 
+impl<T> Drop for BufferObject<T> {
+    fn drop(&mut self) {
+        if self.persistent_map.is_some() {
+            self.bind();
+            unsafe {
+                gl::UnmapBuffer(self.kind as GLenum);
+            }
+            self.unbind();
+        }
+    }
+}
+
+/// A GPU fence guarding a section of persistent-mapped writes. `lock` it after writing into a
+/// `persistent_slice`-backed region and issuing the draw/dispatch that reads it; `wait` on the
+/// next frame before writing into that same region again, so the CPU doesn't race the GPU.
+pub struct BufferFence(GLsync);
+
+impl BufferFence {
+    pub fn lock() -> Self {
+        unsafe { Self(gl::FenceSync(gl::SYNC_GPU_COMMANDS_COMPLETE, 0)) }
+    }
+
+    pub fn wait(self) {
+        unsafe {
+            loop {
+                let result = gl::ClientWaitSync(self.0, gl::SYNC_FLUSH_COMMANDS_BIT, 1_000_000_000);
+                if result == gl::ALREADY_SIGNALED || result == gl::CONDITION_SATISFIED {
+                    break;
+                }
+            }
+            gl::DeleteSync(self.0);
+        }
+    }
+}
+
 #[repr(u32)]
 #[derive(Copy, Clone)]
 pub enum BufferType {
@@ -0,0 +1,46 @@
+use glm::{Mat4, Vec3};
+
+pub struct Camera {
+    pub position: Vec3,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub fov: f32,
+    pub aspect: f32,
+    pub near: f32,
+    pub far: f32,
+}
+
+impl Camera {
+    pub fn new(position: Vec3, aspect: f32) -> Self {
+        Self {
+            position,
+            yaw: 0.0,
+            pitch: 0.0,
+            fov: 70.0f32.to_radians(),
+            aspect,
+            near: 0.1,
+            far: 1000.0,
+        }
+    }
+
+    pub fn forward(&self) -> Vec3 {
+        Vec3::new(
+            self.pitch.cos() * self.yaw.cos(),
+            self.pitch.sin(),
+            self.pitch.cos() * self.yaw.sin(),
+        )
+    }
+
+    pub fn view_matrix(&self) -> Mat4 {
+        let forward = self.forward();
+        glm::look_at(&self.position, &(self.position + forward), &Vec3::y())
+    }
+
+    pub fn projection_matrix(&self) -> Mat4 {
+        glm::perspective(self.aspect, self.fov, self.near, self.far)
+    }
+
+    pub fn set_aspect(&mut self, aspect: f32) {
+        self.aspect = aspect;
+    }
+}
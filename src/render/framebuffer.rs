@@ -0,0 +1,174 @@
+use gl::types::{GLenum, GLint, GLsizei, GLuint};
+
+#[repr(i32)]
+#[derive(Copy, Clone)]
+pub enum ColorAttachmentFormat {
+    Rgba8 = gl::RGBA8 as i32,
+    Rgba16F = gl::RGBA16F as i32,
+}
+
+/// An offscreen render target: one or more color attachments plus an optional combined
+/// depth-stencil renderbuffer, sized to match the window so the existing screen quad can sample
+/// it as a post-process pass instead of being drawn to directly.
+pub struct Framebuffer {
+    id: GLuint,
+    color_attachments: Vec<(GLuint, ColorAttachmentFormat)>,
+    depth_stencil: Option<GLuint>,
+    size: (i32, i32),
+}
+
+impl Framebuffer {
+    pub fn new(size: (i32, i32), color_formats: &[ColorAttachmentFormat], with_depth_stencil: bool) -> Self {
+        let mut id = 0;
+        unsafe {
+            gl::GenFramebuffers(1, &mut id);
+        }
+
+        let mut framebuffer = Self {
+            id,
+            color_attachments: Vec::with_capacity(color_formats.len()),
+            depth_stencil: None,
+            size: (0, 0),
+        };
+
+        framebuffer.bind();
+        for &format in color_formats {
+            let texture = Self::alloc_color_attachment(format, size);
+            framebuffer.color_attachments.push((texture, format));
+        }
+
+        if with_depth_stencil {
+            framebuffer.depth_stencil = Some(Self::alloc_depth_stencil(size));
+        }
+
+        framebuffer.attach_all(size);
+        framebuffer.unbind();
+
+        framebuffer
+    }
+
+    fn alloc_color_attachment(format: ColorAttachmentFormat, size: (i32, i32)) -> GLuint {
+        let mut texture = 0;
+        unsafe {
+            gl::GenTextures(1, &mut texture);
+            gl::BindTexture(gl::TEXTURE_2D, texture);
+            gl::TexImage2D(
+                gl::TEXTURE_2D,
+                0,
+                format as GLint,
+                size.0 as GLsizei,
+                size.1 as GLsizei,
+                0,
+                gl::RGBA,
+                gl::FLOAT,
+                std::ptr::null(),
+            );
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::LINEAR as GLint);
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::LINEAR as GLint);
+            gl::BindTexture(gl::TEXTURE_2D, 0);
+        }
+        texture
+    }
+
+    fn alloc_depth_stencil(size: (i32, i32)) -> GLuint {
+        let mut renderbuffer = 0;
+        unsafe {
+            gl::GenRenderbuffers(1, &mut renderbuffer);
+            gl::BindRenderbuffer(gl::RENDERBUFFER, renderbuffer);
+            gl::RenderbufferStorage(
+                gl::RENDERBUFFER,
+                gl::DEPTH24_STENCIL8,
+                size.0 as GLsizei,
+                size.1 as GLsizei,
+            );
+            gl::BindRenderbuffer(gl::RENDERBUFFER, 0);
+        }
+        renderbuffer
+    }
+
+    fn attach_all(&mut self, size: (i32, i32)) {
+        self.bind();
+        unsafe {
+            for (i, &(texture, _)) in self.color_attachments.iter().enumerate() {
+                gl::FramebufferTexture2D(
+                    gl::FRAMEBUFFER,
+                    gl::COLOR_ATTACHMENT0 + i as GLenum,
+                    gl::TEXTURE_2D,
+                    texture,
+                    0,
+                );
+            }
+
+            // Every color attachment needs to be named here or fragment output past
+            // COLOR_ATTACHMENT0 is silently discarded.
+            let draw_buffers: Vec<GLenum> = (0..self.color_attachments.len())
+                .map(|i| gl::COLOR_ATTACHMENT0 + i as GLenum)
+                .collect();
+            gl::DrawBuffers(draw_buffers.len() as GLsizei, draw_buffers.as_ptr());
+
+            if let Some(renderbuffer) = self.depth_stencil {
+                gl::FramebufferRenderbuffer(
+                    gl::FRAMEBUFFER,
+                    gl::DEPTH_STENCIL_ATTACHMENT,
+                    gl::RENDERBUFFER,
+                    renderbuffer,
+                );
+            }
+        }
+        self.size = size;
+    }
+
+    pub fn resize(&mut self, size: (i32, i32)) {
+        if size == self.size {
+            return;
+        }
+
+        for i in 0..self.color_attachments.len() {
+            let (old_texture, format) = self.color_attachments[i];
+            unsafe {
+                gl::DeleteTextures(1, &old_texture);
+            }
+            self.color_attachments[i] = (Self::alloc_color_attachment(format, size), format);
+        }
+
+        if let Some(old_renderbuffer) = self.depth_stencil.take() {
+            unsafe {
+                gl::DeleteRenderbuffers(1, &old_renderbuffer);
+            }
+            self.depth_stencil = Some(Self::alloc_depth_stencil(size));
+        }
+
+        self.attach_all(size);
+        self.unbind();
+    }
+
+    pub fn bind(&self) {
+        unsafe {
+            gl::BindFramebuffer(gl::FRAMEBUFFER, self.id);
+        }
+    }
+
+    pub fn unbind(&self) {
+        unsafe {
+            gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
+        }
+    }
+
+    pub fn color_texture(&self, index: usize) -> GLuint {
+        self.color_attachments[index].0
+    }
+}
+
+impl Drop for Framebuffer {
+    fn drop(&mut self) {
+        unsafe {
+            for &(texture, _) in &self.color_attachments {
+                gl::DeleteTextures(1, &texture);
+            }
+            if let Some(renderbuffer) = self.depth_stencil {
+                gl::DeleteRenderbuffers(1, &renderbuffer);
+            }
+            gl::DeleteFramebuffers(1, &self.id);
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use std::collections::HashSet;
+use sdl2::keyboard::Scancode;
+use sdl2::mouse::MouseButton as SdlMouseButton;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Key {
+    A, B, C, D, E, F, G, H, I, J, K, L, M,
+    N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
+    Num0, Num1, Num2, Num3, Num4, Num5, Num6, Num7, Num8, Num9,
+    Up, Down, Left, Right,
+    Space, Enter, Escape, Tab, Backspace,
+    LShift, RShift, LCtrl, RCtrl, LAlt, RAlt,
+    F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12,
+}
+
+impl Key {
+    pub fn from_sdl(scancode: Scancode) -> Option<Key> {
+        Some(match scancode {
+            Scancode::A => Key::A,
+            Scancode::B => Key::B,
+            Scancode::C => Key::C,
+            Scancode::D => Key::D,
+            Scancode::E => Key::E,
+            Scancode::F => Key::F,
+            Scancode::G => Key::G,
+            Scancode::H => Key::H,
+            Scancode::I => Key::I,
+            Scancode::J => Key::J,
+            Scancode::K => Key::K,
+            Scancode::L => Key::L,
+            Scancode::M => Key::M,
+            Scancode::N => Key::N,
+            Scancode::O => Key::O,
+            Scancode::P => Key::P,
+            Scancode::Q => Key::Q,
+            Scancode::R => Key::R,
+            Scancode::S => Key::S,
+            Scancode::T => Key::T,
+            Scancode::U => Key::U,
+            Scancode::V => Key::V,
+            Scancode::W => Key::W,
+            Scancode::X => Key::X,
+            Scancode::Y => Key::Y,
+            Scancode::Z => Key::Z,
+            Scancode::Num0 => Key::Num0,
+            Scancode::Num1 => Key::Num1,
+            Scancode::Num2 => Key::Num2,
+            Scancode::Num3 => Key::Num3,
+            Scancode::Num4 => Key::Num4,
+            Scancode::Num5 => Key::Num5,
+            Scancode::Num6 => Key::Num6,
+            Scancode::Num7 => Key::Num7,
+            Scancode::Num8 => Key::Num8,
+            Scancode::Num9 => Key::Num9,
+            Scancode::Up => Key::Up,
+            Scancode::Down => Key::Down,
+            Scancode::Left => Key::Left,
+            Scancode::Right => Key::Right,
+            Scancode::Space => Key::Space,
+            Scancode::Return => Key::Enter,
+            Scancode::Escape => Key::Escape,
+            Scancode::Tab => Key::Tab,
+            Scancode::Backspace => Key::Backspace,
+            Scancode::LShift => Key::LShift,
+            Scancode::RShift => Key::RShift,
+            Scancode::LCtrl => Key::LCtrl,
+            Scancode::RCtrl => Key::RCtrl,
+            Scancode::LAlt => Key::LAlt,
+            Scancode::RAlt => Key::RAlt,
+            Scancode::F1 => Key::F1,
+            Scancode::F2 => Key::F2,
+            Scancode::F3 => Key::F3,
+            Scancode::F4 => Key::F4,
+            Scancode::F5 => Key::F5,
+            Scancode::F6 => Key::F6,
+            Scancode::F7 => Key::F7,
+            Scancode::F8 => Key::F8,
+            Scancode::F9 => Key::F9,
+            Scancode::F10 => Key::F10,
+            Scancode::F11 => Key::F11,
+            Scancode::F12 => Key::F12,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+    X1,
+    X2,
+}
+
+impl MouseButton {
+    pub fn from_sdl(button: SdlMouseButton) -> Option<MouseButton> {
+        Some(match button {
+            SdlMouseButton::Left => MouseButton::Left,
+            SdlMouseButton::Right => MouseButton::Right,
+            SdlMouseButton::Middle => MouseButton::Middle,
+            SdlMouseButton::X1 => MouseButton::X1,
+            SdlMouseButton::X2 => MouseButton::X2,
+            _ => return None,
+        })
+    }
+}
+
+/// Backend-agnostic input state, fed by `Game::handle_events` from raw SDL events so that the
+/// rest of the crate (e.g. `GameState`) never has to depend on `sdl2` directly.
+#[derive(Default)]
+pub struct Input {
+    held: HashSet<Key>,
+    pressed: HashSet<Key>,
+    released: HashSet<Key>,
+    mouse_pos: (i32, i32),
+    mouse_delta: (i32, i32),
+    mouse_held: HashSet<MouseButton>,
+    scroll_delta: f32,
+}
+
+impl Input {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Clears per-frame edge state (pressed/released keys, mouse delta, scroll delta). Must be
+    /// called once before draining each frame's events.
+    pub fn begin_frame(&mut self) {
+        self.pressed.clear();
+        self.released.clear();
+        self.mouse_delta = (0, 0);
+        self.scroll_delta = 0.0;
+    }
+
+    pub fn on_key_down(&mut self, key: Key) {
+        if self.held.insert(key) {
+            self.pressed.insert(key);
+        }
+    }
+
+    pub fn on_key_up(&mut self, key: Key) {
+        self.held.remove(&key);
+        self.released.insert(key);
+    }
+
+    pub fn on_mouse_motion(&mut self, x: i32, y: i32, xrel: i32, yrel: i32) {
+        self.mouse_pos = (x, y);
+        self.mouse_delta.0 += xrel;
+        self.mouse_delta.1 += yrel;
+    }
+
+    pub fn on_mouse_button_down(&mut self, button: MouseButton) {
+        self.mouse_held.insert(button);
+    }
+
+    pub fn on_mouse_button_up(&mut self, button: MouseButton) {
+        self.mouse_held.remove(&button);
+    }
+
+    pub fn on_scroll(&mut self, delta: f32) {
+        self.scroll_delta += delta;
+    }
+
+    pub fn is_key_down(&self, key: Key) -> bool {
+        self.held.contains(&key)
+    }
+
+    pub fn was_pressed(&self, key: Key) -> bool {
+        self.pressed.contains(&key)
+    }
+
+    pub fn was_released(&self, key: Key) -> bool {
+        self.released.contains(&key)
+    }
+
+    pub fn is_mouse_button_down(&self, button: MouseButton) -> bool {
+        self.mouse_held.contains(&button)
+    }
+
+    pub const fn mouse_position(&self) -> (i32, i32) {
+        self.mouse_pos
+    }
+
+    pub const fn mouse_delta(&self) -> (i32, i32) {
+        self.mouse_delta
+    }
+
+    pub const fn scroll_delta(&self) -> f32 {
+        self.scroll_delta
+    }
+}
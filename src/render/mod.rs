@@ -0,0 +1,17 @@
+pub mod buffer;
+pub mod shader;
+pub mod color;
+pub mod texture;
+pub mod vertex_arrays;
+pub mod camera;
+pub mod particles;
+pub mod input;
+pub mod framebuffer;
+
+#[repr(u32)]
+#[derive(Copy, Clone)]
+pub enum GlDataType {
+    Float = gl::FLOAT,
+    Int = gl::INT,
+    UnsignedInt = gl::UNSIGNED_INT,
+}
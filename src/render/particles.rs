@@ -0,0 +1,103 @@
+use std::mem::size_of;
+
+use glm::{Mat4, Vec3};
+
+use crate::render::buffer::{BufferObject, BufferType, BufferUsage};
+use crate::render::shader::{ComputeProgram, Program, Shader};
+use crate::render::vertex_arrays::VertexArrayObject;
+use crate::render::GlDataType;
+
+const PARTICLE_SSBO_BINDING: u32 = 0;
+const LOCAL_SIZE: usize = 256;
+
+/// Mirrors the `std430` layout of the `Particle` struct in `particles.comp`: `vec3` has a
+/// 16-byte base alignment, so `vel` lands at offset 16 and the struct is padded to a 32-byte
+/// stride, not the 28 bytes a naive `pos, vel, life` field order would pack to in Rust.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct Particle {
+    pub pos: Vec3,
+    _pad0: f32,
+    pub vel: Vec3,
+    pub life: f32,
+}
+
+const _: () = assert!(size_of::<Particle>() == 32);
+
+pub struct ParticleSystem {
+    buffer: BufferObject<Particle>,
+    vao: VertexArrayObject,
+    compute: ComputeProgram,
+    render_program: Program,
+    count: usize,
+    lifetime: f32,
+}
+
+impl ParticleSystem {
+    pub fn new(
+        compute: ComputeProgram,
+        count: usize,
+        initial_velocity: impl Fn(usize) -> Vec3,
+        lifetime: f32,
+    ) -> Self {
+        let particles: Vec<Particle> = (0..count)
+            .map(|i| Particle {
+                pos: Vec3::new(0., 0., 0.),
+                _pad0: 0.,
+                vel: initial_velocity(i),
+                life: lifetime,
+            })
+            .collect();
+
+        let buffer = BufferObject::with_data(BufferType::ShaderStorage, &particles, BufferUsage::DynamicDraw);
+        buffer.bind_base(PARTICLE_SSBO_BINDING);
+
+        // `particles.vert` expects exactly 3 locations (aPos, aVel, aLife); the `_pad0` std430
+        // gap must not become its own attribute or it shifts every later location by one and
+        // breaks the binding. Declaring `pos` as a 4-wide vector instead absorbs the gap into
+        // attribute 0's stride (the shader's `vec3 aPos` just ignores the fourth component),
+        // which keeps the three declared attributes lined up with `Particle`'s real byte offsets.
+        let vao = VertexArrayObject::new_arrays(&buffer, None, |a| {
+            a.vector(GlDataType::Float, 4); // pos (.w is std430 padding, unused by the shader)
+            a.vector(GlDataType::Float, 3); // vel
+            a.vector(GlDataType::Float, 1); // life
+        });
+
+        let render_program = {
+            let vert = Shader::from_vertex_source(include_str!("../shaders/particles.vert")).unwrap();
+            let frag = Shader::from_frag_source(include_str!("../shaders/particles.frag")).unwrap();
+            Program::from_shaders(&[vert, frag]).unwrap()
+        };
+
+        Self {
+            buffer,
+            vao,
+            compute,
+            render_program,
+            count,
+            lifetime,
+        }
+    }
+
+    pub fn update(&mut self, delta: f32) {
+        self.buffer.bind_base(PARTICLE_SSBO_BINDING);
+        self.compute.get_program_mut().set_float("delta", delta);
+        self.compute.get_program_mut().set_float("lifetime", self.lifetime);
+        self.compute.execute(
+            ((self.count + LOCAL_SIZE - 1) / LOCAL_SIZE) as u32,
+            1,
+            1,
+        );
+    }
+
+    pub fn render(&mut self, view: &Mat4, projection: &Mat4) {
+        self.render_program.set_used();
+        self.render_program.set_mat4("view", view);
+        self.render_program.set_mat4("projection", projection);
+
+        unsafe {
+            self.vao.bind();
+            gl::DrawArrays(gl::POINTS, 0, self.count as i32);
+        }
+    }
+}
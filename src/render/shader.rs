@@ -1,7 +1,24 @@
+use std::cell::Cell;
 use std::collections::HashMap;
 use std::ffi::{CStr, CString};
 use gl::types::{GLchar, GLenum, GLint, GLuint};
-use glm::{Vec2, Vec3};
+use glm::{Mat4, Vec2, Vec3};
+
+thread_local! {
+    // GL is single-threaded per-context here (mirrors `Game` owning the one context), so a
+    // thread-local is equivalent to a shared `Cell<GLuint>` on `Game` without threading `Game`
+    // through every uniform setter call site. Tied to `Game`'s lifecycle via
+    // `reset_current_program_cache`, which `Game::init` calls for every new GL context so a
+    // dropped-and-recreated `Game` on the same thread can't read a stale id left over from the
+    // previous context.
+    static CURRENT_PROGRAM: Cell<GLuint> = Cell::new(0);
+}
+
+/// Invalidates the cached current-program id. Must be called whenever a new GL context is bound
+/// (see `Game::init`) since program ids are only unique within a context.
+pub(crate) fn reset_current_program_cache() {
+    CURRENT_PROGRAM.with(|current| current.set(0));
+}
 
 #[repr(u32)]
 pub enum ShaderType {
@@ -124,9 +141,16 @@ impl Program {
     }
 
     pub fn set_used(&self) {
-        unsafe {
-            gl::UseProgram(self.id)
-        }
+        CURRENT_PROGRAM.with(|current| {
+            if current.get() == self.id {
+                return;
+            }
+
+            unsafe {
+                gl::UseProgram(self.id)
+            }
+            current.set(self.id);
+        });
     }
 
     pub fn get_uniform_location(&mut self, name: &str) -> GLint {
@@ -170,6 +194,12 @@ impl Program {
             gl::Uniform3f(self.get_uniform_location(name), val.x, val.y, val.z);
         }
     }
+    pub fn set_mat4(&mut self, name: &str, m: &Mat4) {
+        self.set_used();
+        unsafe {
+            gl::UniformMatrix4fv(self.get_uniform_location(name), 1, gl::FALSE, m.as_ptr());
+        }
+    }
 
     #[inline]
     pub const fn id(&self) -> GLuint {
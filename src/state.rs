@@ -1,10 +1,13 @@
 use std::borrow::Borrow;
 use std::ptr;
-use glm::Vec2;
+use glm::{Vec2, Vec3};
 use sdl2::event::Event;
-use crate::game::Game;
+use crate::game::{Game, PARTICLE_COUNT};
 use crate::render::buffer::{BufferObject, BufferType, BufferUsage};
+use crate::render::camera::Camera;
+use crate::render::framebuffer::{ColorAttachmentFormat, Framebuffer};
 use crate::render::GlDataType;
+use crate::render::particles::ParticleSystem;
 use crate::render::shader::{ComputeProgram, Program, Shader};
 use crate::render::texture::Texture;
 use crate::render::vertex_arrays::VertexArrayObject;
@@ -13,6 +16,9 @@ use crate::render::vertex_arrays::VertexArrayObject;
 pub struct GameState {
     pub(crate) screen_vao: VertexArrayObject,
     pub(crate) screen_program: Program,
+    pub(crate) camera: Camera,
+    pub(crate) particles: ParticleSystem,
+    pub(crate) scene_fbo: Framebuffer,
 }
 
 impl GameState {
@@ -77,21 +83,61 @@ impl GameState {
             })
         };
 
+        let aspect = window_size.0 as f32 / window_size.1.max(1) as f32;
+
+        let particles = {
+            let compute = ComputeProgram::from_source(include_str!("shaders/particles.comp")).unwrap();
+            ParticleSystem::new(
+                compute,
+                PARTICLE_COUNT,
+                |_| Vec3::new(0., 1., 0.),
+                5.0,
+            )
+        };
+
+        let scene_fbo = Framebuffer::new(window_size, &[ColorAttachmentFormat::Rgba16F], true);
+
         Self {
             screen_vao,
             screen_program,
+            camera: Camera::new(Vec3::new(0., 0., 3.), aspect),
+            particles,
+            scene_fbo,
         }
     }
 
+    pub fn resize(&mut self, size: (i32, i32)) {
+        let height = size.1.max(1);
+        self.camera.set_aspect(size.0 as f32 / height as f32);
+        self.scene_fbo.resize(size);
+    }
+
     pub fn handle_event(&mut self, event: Event) -> bool {
         false
     }
 
-    pub fn update(&mut self, game: &mut Game, delta: f64) {}
+    pub fn update(&mut self, game: &mut Game, delta: f64) {
+        self.particles.update(delta as f32);
+    }
 
     pub fn render(&mut self, game: &mut Game, delta: f64) {
+        let view = self.camera.view_matrix();
+        let projection = self.camera.projection_matrix();
+
+        self.scene_fbo.bind();
+        unsafe {
+            gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT);
+        }
+        self.particles.render(&view, &projection);
+        self.scene_fbo.unbind();
+
         unsafe {
             self.screen_program.set_used();
+
+            gl::ActiveTexture(gl::TEXTURE0);
+            gl::BindTexture(gl::TEXTURE_2D, self.scene_fbo.color_texture(0));
+            self.screen_program.set_int("screenTexture", 0);
+
             self.screen_vao.bind();
             gl::DrawElements(gl::TRIANGLES, 6, gl::UNSIGNED_INT, ptr::null());
         }